@@ -0,0 +1,151 @@
+use axum::extract::FromRequestParts;
+use axum::http::{HeaderMap, request::Parts};
+use chrono::{Duration, Utc};
+use jsonwebtoken::{DecodingKey, EncodingKey, Validation, decode, encode};
+use serde::{Deserialize, Serialize};
+
+use crate::error::Error;
+use crate::{AppState, User};
+
+/// Signing/verification keys for the HS256 session JWTs, derived once from
+/// the `JWT_SECRET` env var and stored in `AppState`.
+#[derive(Clone)]
+pub struct Keys {
+    pub encoding: EncodingKey,
+    pub decoding: DecodingKey,
+}
+
+impl Keys {
+    pub fn from_env() -> Self {
+        let secret = std::env::var("JWT_SECRET").expect("JWT_SECRET must be set");
+        let bytes = secret.as_bytes();
+        Self {
+            encoding: EncodingKey::from_secret(bytes),
+            decoding: DecodingKey::from_secret(bytes),
+        }
+    }
+}
+
+/// `access` tokens authorize requests; `refresh` tokens only ever mint new
+/// `access` tokens via `POST /auth/refresh`. Keeping the distinction in the
+/// claims (rather than two unrelated secrets) means both kinds round-trip
+/// through the same `decode` call.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TokenKind {
+    Access,
+    Refresh,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct Claims {
+    pub sub: i32,
+    pub kind: TokenKind,
+    pub exp: usize,
+}
+
+const ACCESS_TTL_MINUTES: i64 = 10;
+const REFRESH_TTL_DAYS: i64 = 30;
+
+#[derive(Debug, Serialize, utoipa::ToSchema)]
+pub struct TokenPair {
+    pub access_token: String,
+    pub refresh_token: String,
+}
+
+pub fn issue_token_pair(keys: &Keys, schueler_id: i32) -> Result<TokenPair, jsonwebtoken::errors::Error> {
+    Ok(TokenPair {
+        access_token: issue_access_token(keys, schueler_id)?,
+        refresh_token: issue(
+            keys,
+            schueler_id,
+            TokenKind::Refresh,
+            REFRESH_TTL_DAYS * 24 * 60,
+        )?,
+    })
+}
+
+pub fn issue_access_token(keys: &Keys, schueler_id: i32) -> Result<String, jsonwebtoken::errors::Error> {
+    issue(keys, schueler_id, TokenKind::Access, ACCESS_TTL_MINUTES)
+}
+
+fn issue(
+    keys: &Keys,
+    schueler_id: i32,
+    kind: TokenKind,
+    ttl_minutes: i64,
+) -> Result<String, jsonwebtoken::errors::Error> {
+    let exp = (Utc::now() + Duration::minutes(ttl_minutes)).timestamp() as usize;
+    let claims = Claims {
+        sub: schueler_id,
+        kind,
+        exp,
+    };
+
+    encode(&jsonwebtoken::Header::default(), &claims, &keys.encoding)
+}
+
+pub fn decode_claims(keys: &Keys, token: &str) -> Result<Claims, jsonwebtoken::errors::Error> {
+    decode::<Claims>(token, &keys.decoding, &Validation::new(jsonwebtoken::Algorithm::HS256))
+        .map(|data| data.claims)
+}
+
+pub fn read_bearer_token(headers: &HeaderMap) -> Result<&str, Error> {
+    let auth = headers
+        .get(axum::http::header::AUTHORIZATION)
+        .and_then(|v| v.to_str().ok())
+        .ok_or_else(|| Error::Unauthorized("Missing Authorization header".to_string()))?;
+
+    let token = auth
+        .strip_prefix("Bearer ")
+        .ok_or_else(|| Error::Unauthorized("Expected: Bearer <token>".to_string()))?
+        .trim();
+
+    if token.is_empty() {
+        return Err(Error::Unauthorized("Empty token".to_string()));
+    }
+
+    Ok(token)
+}
+
+/// Decodes and validates a bearer JWT, requiring it to carry `kind`, and
+/// returns the `schueler.id` from its `sub` claim. The token's own `exp`
+/// enforces the session window, so there is no server-side state to touch
+/// or expire here.
+pub fn require_token_kind(state: &AppState, token: &str, kind: TokenKind) -> Result<i32, Error> {
+    let claims = decode_claims(&state.keys, token)
+        .map_err(|_| Error::Unauthorized("Invalid or expired token".to_string()))?;
+
+    if claims.kind != kind {
+        return Err(Error::Unauthorized(format!("Expected a {kind:?} token")));
+    }
+
+    Ok(claims.sub)
+}
+
+/// Authenticates a request via its bearer access JWT and loads the
+/// corresponding `schueler` row, so handlers can take `user: AuthUser`
+/// instead of repeating the header/claims/lookup dance themselves.
+pub struct AuthUser(pub User);
+
+impl FromRequestParts<AppState> for AuthUser {
+    type Rejection = Error;
+
+    async fn from_request_parts(
+        parts: &mut Parts,
+        state: &AppState,
+    ) -> Result<Self, Self::Rejection> {
+        let token = read_bearer_token(&parts.headers)?;
+        let schueler_id = require_token_kind(state, token, TokenKind::Access)?;
+        let user = state
+            .users
+            .find_by_id(schueler_id)
+            .await
+            .map_err(|e| match e {
+                Error::NotFound => Error::Unauthorized("Unknown user".to_string()),
+                e => e,
+            })?;
+
+        Ok(AuthUser(user))
+    }
+}
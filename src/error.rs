@@ -0,0 +1,65 @@
+use axum::Json;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use serde_json::json;
+
+/// Single error type for the whole API surface. Handlers return
+/// `Result<T, Error>` and let `IntoResponse` pick the status code, instead
+/// of hand-rolling `(StatusCode, String)` tuples (and leaking raw SQL
+/// errors to clients) at every call site.
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("not found")]
+    NotFound,
+
+    #[error("{0}")]
+    Unauthorized(String),
+
+    #[error("{0}")]
+    Validation(String),
+
+    #[error("a user with that email already exists")]
+    UserExists,
+
+    #[error("{0}")]
+    Internal(String),
+
+    #[error("database error: {0}")]
+    Sqlx(sqlx::Error),
+}
+
+impl From<sqlx::Error> for Error {
+    fn from(err: sqlx::Error) -> Self {
+        match err {
+            sqlx::Error::RowNotFound => Error::NotFound,
+            sqlx::Error::Database(db_err) if db_err.is_unique_violation() => Error::UserExists,
+            err => Error::Sqlx(err),
+        }
+    }
+}
+
+impl IntoResponse for Error {
+    fn into_response(self) -> Response {
+        let status = match &self {
+            Error::NotFound => StatusCode::NOT_FOUND,
+            Error::Unauthorized(_) => StatusCode::UNAUTHORIZED,
+            Error::Validation(_) => StatusCode::BAD_REQUEST,
+            Error::UserExists => StatusCode::CONFLICT,
+            Error::Internal(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            Error::Sqlx(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+
+        // `Sqlx`/`Internal` can wrap things like connection strings, column
+        // names, or raw SMTP transport errors, so only their `Display` is
+        // fit to print to our own logs, never to the client.
+        let message = match &self {
+            Error::Sqlx(_) | Error::Internal(_) => {
+                eprintln!("internal error: {self}");
+                "internal server error".to_string()
+            }
+            _ => self.to_string(),
+        };
+
+        (status, Json(json!({ "message": message }))).into_response()
+    }
+}
@@ -0,0 +1,114 @@
+use async_trait::async_trait;
+use lettre::message::Mailbox;
+use lettre::transport::smtp::authentication::Credentials;
+use lettre::{AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor};
+
+use crate::error::Error;
+
+/// Sends the rider's contact request to an entry's poster. Kept behind a
+/// trait (rather than calling `lettre` directly from the handler) so tests
+/// can swap in a fake that just captures what would have been sent.
+#[async_trait]
+pub trait Mailer: Send + Sync {
+    async fn send_contact_request(
+        &self,
+        to: &str,
+        requester_email: &str,
+        message: &str,
+    ) -> Result<(), Error>;
+}
+
+pub struct SmtpMailer {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+    from: Mailbox,
+}
+
+impl SmtpMailer {
+    pub fn from_env() -> Self {
+        let host = std::env::var("SMTP_HOST").expect("SMTP_HOST must be set");
+        let username = std::env::var("SMTP_USERNAME").expect("SMTP_USERNAME must be set");
+        let password = std::env::var("SMTP_PASSWORD").expect("SMTP_PASSWORD must be set");
+        let from = std::env::var("SMTP_FROM").expect("SMTP_FROM must be set");
+
+        let transport = AsyncSmtpTransport::<Tokio1Executor>::relay(&host)
+            .expect("invalid SMTP_HOST")
+            .credentials(Credentials::new(username, password))
+            .build();
+
+        Self {
+            transport,
+            from: from.parse().expect("SMTP_FROM must be a valid mailbox"),
+        }
+    }
+}
+
+#[async_trait]
+impl Mailer for SmtpMailer {
+    async fn send_contact_request(
+        &self,
+        to: &str,
+        requester_email: &str,
+        message: &str,
+    ) -> Result<(), Error> {
+        let to: Mailbox = to
+            .parse()
+            .map_err(|_| Error::Internal("poster has no valid contact email".to_string()))?;
+
+        let email = Message::builder()
+            .from(self.from.clone())
+            .to(to)
+            .subject("New contact request via Mitfahrzentrale")
+            .body(format!("{requester_email} wrote:\n\n{message}"))
+            .map_err(|e| Error::Internal(format!("could not build contact email: {e}")))?;
+
+        self.transport
+            .send(email)
+            .await
+            .map_err(|e| Error::Internal(format!("could not send contact email: {e}")))?;
+
+        Ok(())
+    }
+}
+
+/// A `Mailer` that records what would have been sent instead of making an
+/// SMTP connection, so handler tests can assert on the captured message.
+#[cfg(test)]
+pub struct FakeMailer {
+    pub sent: std::sync::Mutex<Vec<SentEmail>>,
+}
+
+#[cfg(test)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SentEmail {
+    pub to: String,
+    pub requester_email: String,
+    pub message: String,
+}
+
+#[cfg(test)]
+impl FakeMailer {
+    pub fn new() -> Self {
+        Self {
+            sent: std::sync::Mutex::new(Vec::new()),
+        }
+    }
+}
+
+#[cfg(test)]
+#[async_trait]
+impl Mailer for FakeMailer {
+    async fn send_contact_request(
+        &self,
+        to: &str,
+        requester_email: &str,
+        message: &str,
+    ) -> Result<(), Error> {
+        self.sent.lock().unwrap().push(SentEmail {
+            to: to.to_string(),
+            requester_email: requester_email.to_string(),
+            message: message.to_string(),
+        });
+
+        Ok(())
+    }
+}
@@ -1,3 +1,13 @@
+mod auth;
+mod error;
+mod mailer;
+mod openapi;
+mod password;
+mod repository;
+mod short_id;
+
+use std::sync::Arc;
+
 use axum::extract::Path;
 use axum::{
     Json, Router,
@@ -6,24 +16,29 @@ use axum::{
     routing::{get, post},
 };
 use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
 use sqlx::postgres::PgPoolOptions;
-use sqlx::{FromRow, PgPool};
+use utoipa::OpenApi;
+use utoipa::ToSchema;
+use utoipa_swagger_ui::SwaggerUi;
 
-use std::{
-    collections::HashMap,
-    sync::Arc,
-    time::{Duration, Instant},
-};
-use tokio::sync::RwLock;
+use auth::{AuthUser, Keys, TokenKind};
+use error::Error;
+use mailer::{Mailer, SmtpMailer};
+use openapi::ApiDoc;
+use repository::{EntryRepository, UserRepository};
+use short_id::ShortIds;
 
 #[derive(Clone)]
 struct AppState {
-    pool: PgPool,
-    // token -> last activity
-    sessions: Arc<RwLock<HashMap<String, Instant>>>,
+    entries: EntryRepository,
+    users: UserRepository,
+    keys: Keys,
+    short_ids: ShortIds,
+    mailer: Arc<dyn Mailer>,
 }
 
-#[derive(Serialize, Deserialize, Debug, FromRow)]
+#[derive(Debug, FromRow)]
 struct Entry {
     id: i32,
     titel: String,
@@ -32,7 +47,30 @@ struct Entry {
     sitzplaetze: i32,
 }
 
-#[derive(Deserialize, Debug)]
+/// The public projection of `Entry`: the same fields, but with the
+/// internal sequential `id` swapped for its opaque sqids encoding.
+#[derive(Serialize, Debug, ToSchema)]
+struct EntryResponse {
+    id: String,
+    titel: String,
+    nachricht: String,
+    typ: String,
+    sitzplaetze: i32,
+}
+
+impl Entry {
+    fn into_response(self, short_ids: &ShortIds) -> EntryResponse {
+        EntryResponse {
+            id: short_ids.encode(self.id),
+            titel: self.titel,
+            nachricht: self.nachricht,
+            typ: self.typ,
+            sitzplaetze: self.sitzplaetze,
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, ToSchema)]
 struct NewEntry {
     titel: String,
     nachricht: String,
@@ -42,20 +80,62 @@ struct NewEntry {
     // schueler_id: i32,
 }
 
-#[derive(Serialize, Deserialize, Debug, FromRow)]
+#[derive(Debug, FromRow)]
 struct User {
     id: i32,
     nachname: String,
     email: String,
     status: String,
+    // Read by `SELECT *` for the `FromRow` mapping, but only ever matched
+    // against in SQL (`WHERE token = $2`) now that claiming clears it.
+    #[allow(dead_code)]
     token: String,
+    password_hash: String,
+}
+
+/// The public projection of `User` handed back to clients. `token` and
+/// `password_hash` are credentials, not profile data, and must never be
+/// serialized (that's how `list_users` used to leak every login).
+#[derive(Serialize, Debug, ToSchema)]
+struct PublicUser {
+    id: i32,
+    nachname: String,
+    email: String,
+    status: String,
 }
 
-#[derive(Serialize, Deserialize, Debug, FromRow)]
+impl From<User> for PublicUser {
+    fn from(user: User) -> Self {
+        Self {
+            id: user.id,
+            nachname: user.nachname,
+            email: user.email,
+            status: user.status,
+        }
+    }
+}
+
+#[derive(Deserialize, Debug, ToSchema)]
+struct LoginRequest {
+    email: String,
+    password: String,
+}
+
+#[derive(Deserialize, Debug, ToSchema)]
+struct ClaimAccountRequest {
+    password: String,
+}
+
+#[derive(Debug, FromRow)]
 struct EntryContact {
     email: String,
 }
 
+#[derive(Deserialize, Debug, ToSchema)]
+struct ContactRequest {
+    message: String,
+}
+
 #[tokio::main]
 async fn main() -> Result<(), sqlx::Error> {
     let pool = PgPoolOptions::new()
@@ -64,16 +144,22 @@ async fn main() -> Result<(), sqlx::Error> {
         .await?;
 
     let state = AppState {
-        pool,
-        sessions: Arc::new(RwLock::new(HashMap::new())),
+        entries: EntryRepository::new(pool.clone()),
+        users: UserRepository::new(pool),
+        keys: Keys::from_env(),
+        short_ids: ShortIds::from_env(),
+        mailer: Arc::new(SmtpMailer::from_env()),
     };
 
     let app = Router::new()
-        .route("/login/{token}", post(login_with_token))
+        .route("/auth/claim/{token}", post(claim_account))
+        .route("/auth/login", post(login_with_password))
+        .route("/auth/refresh", post(refresh_access_token))
         .route("/users", get(list_users))
         .route("/entries/{id}", get(get_entry_by_id))
-        .route("/entries/{id}/contact", get(get_entry_contact))
+        .route("/entries/{id}/contact", post(contact_entry))
         .route("/entries", get(list_entries).post(create_entry))
+        .merge(SwaggerUi::new("/swagger-ui").url("/api-doc/openapi.json", ApiDoc::openapi()))
         .with_state(state);
 
     let listener = tokio::net::TcpListener::bind("0.0.0.0:3000").await.unwrap();
@@ -82,200 +168,206 @@ async fn main() -> Result<(), sqlx::Error> {
     Ok(())
 }
 
-// ---------- AUTH HELPERS ----------
-
-fn read_bearer_token(headers: &HeaderMap) -> Result<&str, (StatusCode, String)> {
-    let auth = headers
-        .get(axum::http::header::AUTHORIZATION)
-        .and_then(|v| v.to_str().ok())
-        .ok_or((
-            StatusCode::UNAUTHORIZED,
-            "Missing Authorization header".to_string(),
-        ))?;
-
-    let token = auth
-        .strip_prefix("Bearer ")
-        .ok_or((
-            StatusCode::UNAUTHORIZED,
-            "Expected: Bearer <token>".to_string(),
-        ))?
-        .trim();
-
-    if token.is_empty() {
-        return Err((StatusCode::UNAUTHORIZED, "Empty token".to_string()));
-    }
-
-    Ok(token)
-}
-
-async fn token_exists(pool: &PgPool, token: &str) -> Result<bool, sqlx::Error> {
-    let (exists,) =
-        sqlx::query_as::<_, (bool,)>(r#"SELECT EXISTS(SELECT 1 FROM schueler WHERE token = $1)"#)
-            .bind(token)
-            .fetch_one(pool)
-            .await?;
+// ---------- LOGIN ENDPOINT ----------
 
-    Ok(exists)
-}
+/// One-time bootstrap for accounts that still only have the legacy
+/// `schueler.token`: spends that token to set a real `password_hash`
+/// instead of granting a session by itself, so `token` stops being a
+/// standing, unhashed credential alongside the email/password login.
+#[utoipa::path(
+    post,
+    path = "/auth/claim/{token}",
+    params(("token" = String, Path, description = "One-time legacy token issued to the schueler")),
+    request_body = ClaimAccountRequest,
+    responses(
+        (status = 200, description = "Password set; access/refresh token pair issued", body = auth::TokenPair),
+        (status = 401, description = "Invalid or already-claimed token"),
+    ),
+    tag = "mitfahrzentrale",
+)]
+async fn claim_account(
+    Path(token): Path<String>,
+    State(state): State<AppState>,
+    Json(payload): Json<ClaimAccountRequest>,
+) -> Result<Json<auth::TokenPair>, Error> {
+    let password_hash = password::hash(&payload.password);
+    let user = state
+        .users
+        .claim_token(&token, &password_hash)
+        .await
+        .map_err(|_| Error::Unauthorized("Invalid token".to_string()))?;
 
-/// Requires that:
-/// 1) token is present in memory (user called /login/{token})
-/// 2) last activity is not older than 10 minutes
-/// Then it "touches" (refreshes) activity time.
-async fn require_logged_in_and_touch(
-    state: &AppState,
-    token: &str,
-) -> Result<(), (StatusCode, String)> {
-    let idle_limit = Duration::from_secs(10 * 60);
-
-    let mut sessions = state.sessions.write().await;
-
-    let last = sessions.get(token).copied().ok_or((
-        StatusCode::UNAUTHORIZED,
-        "Not logged in. Call POST /login/{token}".to_string(),
-    ))?;
-
-    if last.elapsed() > idle_limit {
-        sessions.remove(token); // expire session
-        return Err((
-            StatusCode::UNAUTHORIZED,
-            "Session expired (idle > 10 min). Call POST /login/{token} again.".to_string(),
-        ));
-    }
+    let pair = auth::issue_token_pair(&state.keys, user.id)
+        .map_err(|_| Error::Unauthorized("Could not issue token".to_string()))?;
 
-    // touch
-    sessions.insert(token.to_string(), Instant::now());
-    Ok(())
+    Ok(Json(pair))
 }
 
-// ---------- LOGIN ENDPOINT ----------
-
-async fn login_with_token(
-    Path(token): Path<String>,
+#[utoipa::path(
+    post,
+    path = "/auth/login",
+    request_body = LoginRequest,
+    responses(
+        (status = 200, description = "Access/refresh token pair issued", body = auth::TokenPair),
+        (status = 401, description = "Invalid email or password"),
+    ),
+    tag = "mitfahrzentrale",
+)]
+async fn login_with_password(
     State(state): State<AppState>,
-) -> Result<Json<serde_json::Value>, (StatusCode, String)> {
-    // 1) token must exist in DB
-    let exists = token_exists(&state.pool, &token)
-        .await
-        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+    Json(payload): Json<LoginRequest>,
+) -> Result<Json<auth::TokenPair>, Error> {
+    let user = state.users.find_by_email(&payload.email).await;
+
+    // Hash/verify against a dummy hash on the not-found path too, so a
+    // wrong-password response and a no-such-email response take the same
+    // time and can't be used to enumerate registered emails.
+    let Ok(user) = user else {
+        password::verify(&payload.password, password::dummy_hash());
+        return Err(Error::Unauthorized("Invalid email or password".to_string()));
+    };
 
-    if !exists {
-        return Err((StatusCode::UNAUTHORIZED, "Invalid token".to_string()));
+    if !password::verify(&payload.password, &user.password_hash) {
+        return Err(Error::Unauthorized("Invalid email or password".to_string()));
     }
 
-    // 2) mark as logged in (start session)
-    {
-        let mut sessions = state.sessions.write().await;
-        sessions.insert(token, Instant::now());
-    }
+    let pair = auth::issue_token_pair(&state.keys, user.id)
+        .map_err(|_| Error::Unauthorized("Could not issue token".to_string()))?;
 
-    Ok(Json(serde_json::json!({ "ok": true })))
+    Ok(Json(pair))
 }
 
-// ---------- DB QUERIES ----------
-
-async fn get_all_entries(pool: &sqlx::PgPool) -> Result<Vec<Entry>, sqlx::Error> {
-    sqlx::query_as::<_, Entry>(
-        r#"
-        SELECT id, titel, nachricht, typ, sitzplaetze
-        FROM eintrag
-        "#,
-    )
-    .fetch_all(pool)
-    .await
-}
+#[utoipa::path(
+    post,
+    path = "/auth/refresh",
+    responses(
+        (status = 200, description = "New access token issued"),
+        (status = 401, description = "Missing, invalid, or non-refresh bearer token"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "mitfahrzentrale",
+)]
+async fn refresh_access_token(
+    headers: HeaderMap,
+    State(state): State<AppState>,
+) -> Result<Json<serde_json::Value>, Error> {
+    let refresh_token = auth::read_bearer_token(&headers)?;
+    let schueler_id = auth::require_token_kind(&state, refresh_token, TokenKind::Refresh)?;
+
+    let access_token = auth::issue_access_token(&state.keys, schueler_id)
+        .map_err(|_| Error::Unauthorized("Could not issue token".to_string()))?;
 
-async fn get_all_users(pool: &sqlx::PgPool) -> Result<Vec<User>, sqlx::Error> {
-    sqlx::query_as::<_, User>(
-        r#"
-        SELECT id, nachname, email, status, token
-        FROM schueler
-        "#,
-    )
-    .fetch_all(pool)
-    .await
+    Ok(Json(serde_json::json!({ "access_token": access_token })))
 }
 
 // ---------- HANDLERS ----------
 
+#[utoipa::path(
+    get,
+    path = "/entries",
+    responses(
+        (status = 200, description = "All entries", body = [EntryResponse]),
+    ),
+    tag = "mitfahrzentrale",
+)]
 async fn list_entries(
     State(state): State<AppState>,
-) -> Result<Json<Vec<Entry>>, (StatusCode, String)> {
-    let entries = get_all_entries(&state.pool)
-        .await
-        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+) -> Result<Json<Vec<EntryResponse>>, Error> {
+    let entries = state
+        .entries
+        .list()
+        .await?
+        .into_iter()
+        .map(|entry| entry.into_response(&state.short_ids))
+        .collect();
 
     Ok(Json(entries))
 }
 
+#[utoipa::path(
+    get,
+    path = "/users",
+    responses(
+        (status = 200, description = "All registered schueler", body = [PublicUser]),
+        (status = 401, description = "Missing or invalid bearer token"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "mitfahrzentrale",
+)]
 async fn list_users(
-    headers: HeaderMap,
+    _user: AuthUser,
     State(state): State<AppState>,
-) -> Result<Json<Vec<User>>, (StatusCode, String)> {
-    let token = read_bearer_token(&headers)?;
-
-    // must have called /login/{token} and be active within 10 min
-    require_logged_in_and_touch(&state, token).await?;
-
-    let users = get_all_users(&state.pool)
-        .await
-        .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
+) -> Result<Json<Vec<PublicUser>>, Error> {
+    let users = state
+        .users
+        .list()
+        .await?
+        .into_iter()
+        .map(PublicUser::from)
+        .collect();
 
     Ok(Json(users))
 }
 
+#[utoipa::path(
+    get,
+    path = "/entries/{id}",
+    params(("id" = String, Path, description = "Opaque sqids-encoded entry id")),
+    responses(
+        (status = 200, description = "The requested entry", body = EntryResponse),
+        (status = 401, description = "Missing or invalid bearer token"),
+        (status = 404, description = "No entry with that id"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "mitfahrzentrale",
+)]
 async fn get_entry_by_id(
-    Path(id): Path<i32>,
-    headers: HeaderMap,
+    Path(slug): Path<String>,
+    _user: AuthUser,
     State(state): State<AppState>,
-) -> Result<Json<Entry>, (StatusCode, String)> {
-    let token = read_bearer_token(&headers)?;
-    require_logged_in_and_touch(&state, token).await?;
-
-    let entry = sqlx::query_as::<_, Entry>(
-        r#"
-            SELECT id, titel, nachricht, typ, sitzplaetze
-            FROM eintrag
-            WHERE id=$1
-        "#,
-    )
-    .bind(id)
-    .fetch_one(&state.pool)
-    .await
-    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
-
-    Ok(Json(entry))
+) -> Result<Json<EntryResponse>, Error> {
+    let id = state.short_ids.decode(&slug).ok_or(Error::NotFound)?;
+    let entry = state.entries.find_by_id(id).await?;
+
+    Ok(Json(entry.into_response(&state.short_ids)))
 }
 
-async fn get_entry_contact(
-    Path(id): Path<i32>,
-    headers: HeaderMap,
+#[utoipa::path(
+    post,
+    path = "/entries/{id}/contact",
+    params(("id" = String, Path, description = "Opaque sqids-encoded entry id")),
+    request_body = ContactRequest,
+    responses(
+        (status = 202, description = "Contact request emailed to the poster"),
+        (status = 400, description = "Invalid request body"),
+        (status = 401, description = "Missing or invalid bearer token"),
+        (status = 404, description = "No entry with that id"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "mitfahrzentrale",
+)]
+async fn contact_entry(
+    Path(slug): Path<String>,
+    AuthUser(requester): AuthUser,
     State(state): State<AppState>,
-) -> Result<Json<EntryContact>, (StatusCode, String)> {
-    let token = read_bearer_token(&headers)?;
-    require_logged_in_and_touch(&state, token).await?;
-
-    let contact = sqlx::query_as::<_, EntryContact>(
-        r#"
-            SELECT schueler.email
-            FROM eintrag
-            inner join schueler on eintrag.schueler_id=schueler.id where eintrag.id=$1;
-        "#,
-    )
-    .bind(id)
-    .fetch_one(&state.pool)
-    .await
-    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
-
-    Ok(Json(contact))
+    Json(payload): Json<ContactRequest>,
+) -> Result<StatusCode, Error> {
+    let id = state.short_ids.decode(&slug).ok_or(Error::NotFound)?;
+    let contact = state.entries.contact_email(id).await?;
+
+    state
+        .mailer
+        .send_contact_request(&contact.email, &requester.email, &payload.message)
+        .await?;
+
+    Ok(StatusCode::ACCEPTED)
 }
 
-fn validate_entry_payload(p: &NewEntry) -> Result<(), (StatusCode, String)> {
+fn validate_entry_payload(p: &NewEntry) -> Result<(), Error> {
     // Content filter (case-insensitive)
     let msg = p.nachricht.to_lowercase();
     if msg.contains("werbung") || msg.contains("verkauf") {
-        return Err((
-            StatusCode::BAD_REQUEST,
+        return Err(Error::Validation(
             "Entry rejected: advertising/selling content".to_string(),
         ));
     }
@@ -284,8 +376,7 @@ fn validate_entry_payload(p: &NewEntry) -> Result<(), (StatusCode, String)> {
     match p.typ.as_str() {
         "Angebot" => {
             if p.sitzplaetze <= 0 {
-                return Err((
-                    StatusCode::BAD_REQUEST,
+                return Err(Error::Validation(
                     "Typ 'Angebot' requires sitzplaetze > 0".to_string(),
                 ));
             }
@@ -294,8 +385,7 @@ fn validate_entry_payload(p: &NewEntry) -> Result<(), (StatusCode, String)> {
             // no special requirement
         }
         _ => {
-            return Err((
-                StatusCode::BAD_REQUEST,
+            return Err(Error::Validation(
                 "typ must be 'Angebot' or 'Anfrage'".to_string(),
             ));
         }
@@ -303,14 +393,10 @@ fn validate_entry_payload(p: &NewEntry) -> Result<(), (StatusCode, String)> {
 
     // optional: basic checks
     if p.titel.trim().is_empty() {
-        return Err((
-            StatusCode::BAD_REQUEST,
-            "titel must not be empty".to_string(),
-        ));
+        return Err(Error::Validation("titel must not be empty".to_string()));
     }
     if p.nachricht.trim().is_empty() {
-        return Err((
-            StatusCode::BAD_REQUEST,
+        return Err(Error::Validation(
             "nachricht must not be empty".to_string(),
         ));
     }
@@ -318,51 +404,132 @@ fn validate_entry_payload(p: &NewEntry) -> Result<(), (StatusCode, String)> {
     Ok(())
 }
 
+#[utoipa::path(
+    post,
+    path = "/entries",
+    request_body = NewEntry,
+    responses(
+        (status = 201, description = "Entry created", body = EntryResponse),
+        (status = 400, description = "Validation failed, e.g. empty titel or sitzplaetze <= 0 for an 'Angebot'"),
+        (status = 401, description = "Missing or invalid bearer token"),
+    ),
+    security(("bearer_auth" = [])),
+    tag = "mitfahrzentrale",
+)]
 async fn create_entry(
-    headers: HeaderMap,
+    AuthUser(user): AuthUser,
     State(state): State<AppState>,
     Json(payload): Json<NewEntry>,
-) -> Result<(StatusCode, Json<Entry>), (StatusCode, String)> {
-    // Token-check: must be logged in + not idle
-    let token = read_bearer_token(&headers)?;
-    let user = get_user_by_token(token, &state.pool).await.unwrap();
-    require_logged_in_and_touch(&state, token).await?;
-
+) -> Result<(StatusCode, Json<EntryResponse>), Error> {
     // Business validation
     validate_entry_payload(&payload)?;
 
-    // Insert into DB
-    // NOTE: adjust columns if your eintrag table differs (e.g. schueler_id)
-    let inserted = sqlx::query_as::<_, Entry>(
-        r#"
-        INSERT INTO eintrag (titel, nachricht, typ, sitzplaetze, schueler_id)
-        VALUES ($1, $2, $3, $4, $5)
-        RETURNING id, titel, nachricht, typ, sitzplaetze
-        "#,
-    )
-    .bind(payload.titel)
-    .bind(payload.nachricht)
-    .bind(payload.typ)
-    .bind(payload.sitzplaetze)
-    .bind(user.id)
-    .fetch_one(&state.pool)
-    .await
-    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
-
-    Ok((StatusCode::CREATED, Json(inserted)))
+    let inserted = state.entries.create(payload, user.id).await?;
+
+    Ok((
+        StatusCode::CREATED,
+        Json(inserted.into_response(&state.short_ids)),
+    ))
 }
 
-async fn get_user_by_token(token: &str, pool: &PgPool) -> Result<User, (StatusCode, String)> {
-    let user = sqlx::query_as::<_, User>(
-        r#"
-            SELECT * from schueler
-            WHERE token=$1
-        "#,
-    )
-    .bind(token)
-    .fetch_one(pool)
-    .await
-    .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, e.to_string()))?;
-
-    Ok(user)
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use jsonwebtoken::{DecodingKey, EncodingKey};
+    use sqlx::PgPool;
+
+    use super::*;
+    use crate::mailer::FakeMailer;
+
+    #[sqlx::test(migrations = false)]
+    async fn contact_entry_emails_the_poster_without_revealing_their_address(pool: PgPool) {
+        sqlx::query(
+            r#"
+            CREATE TABLE schueler (
+                id SERIAL PRIMARY KEY,
+                nachname TEXT NOT NULL,
+                email TEXT NOT NULL,
+                status TEXT NOT NULL,
+                token TEXT NOT NULL,
+                password_hash TEXT NOT NULL DEFAULT ''
+            )
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        sqlx::query(
+            r#"
+            CREATE TABLE eintrag (
+                id SERIAL PRIMARY KEY,
+                titel TEXT NOT NULL,
+                nachricht TEXT NOT NULL,
+                typ TEXT NOT NULL,
+                sitzplaetze INT NOT NULL,
+                schueler_id INT NOT NULL REFERENCES schueler(id)
+            )
+            "#,
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let (poster_id,): (i32,) = sqlx::query_as(
+            "INSERT INTO schueler (nachname, email, status, token) VALUES ('Poster', 'poster@example.com', 'active', 'tok-poster') RETURNING id",
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+
+        let (requester_id,): (i32,) = sqlx::query_as(
+            "INSERT INTO schueler (nachname, email, status, token) VALUES ('Requester', 'requester@example.com', 'active', 'tok-requester') RETURNING id",
+        )
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+
+        let (entry_id,): (i32,) = sqlx::query_as(
+            "INSERT INTO eintrag (titel, nachricht, typ, sitzplaetze, schueler_id) VALUES ('Fahrt nach Koeln', 'Suche Mitfahrer', 'Angebot', 2, $1) RETURNING id",
+        )
+        .bind(poster_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+
+        let mailer = Arc::new(FakeMailer::new());
+        let state = AppState {
+            entries: EntryRepository::new(pool.clone()),
+            users: UserRepository::new(pool.clone()),
+            keys: Keys {
+                encoding: EncodingKey::from_secret(b"test-secret"),
+                decoding: DecodingKey::from_secret(b"test-secret"),
+            },
+            short_ids: ShortIds::from_env(),
+            mailer: mailer.clone(),
+        };
+
+        let slug = state.short_ids.encode(entry_id);
+        let requester = state.users.find_by_id(requester_id).await.unwrap();
+
+        let status = contact_entry(
+            Path(slug),
+            AuthUser(requester),
+            State(state),
+            Json(ContactRequest {
+                message: "Is this ride still open?".to_string(),
+            }),
+        )
+        .await
+        .expect("contact_entry should succeed");
+
+        assert_eq!(status, StatusCode::ACCEPTED);
+
+        let sent = mailer.sent.lock().unwrap();
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].to, "poster@example.com");
+        assert_eq!(sent[0].requester_email, "requester@example.com");
+        assert_eq!(sent[0].message, "Is this ride still open?");
+    }
 }
@@ -0,0 +1,54 @@
+use utoipa::Modify;
+use utoipa::openapi::security::{HttpAuthScheme, HttpBuilder, SecurityScheme};
+use utoipa::OpenApi;
+
+use crate::auth::TokenPair;
+use crate::{ClaimAccountRequest, ContactRequest, EntryResponse, LoginRequest, NewEntry, PublicUser};
+
+#[derive(OpenApi)]
+#[openapi(
+    paths(
+        crate::claim_account,
+        crate::login_with_password,
+        crate::refresh_access_token,
+        crate::list_entries,
+        crate::list_users,
+        crate::get_entry_by_id,
+        crate::contact_entry,
+        crate::create_entry,
+    ),
+    components(schemas(
+        TokenPair,
+        EntryResponse,
+        NewEntry,
+        PublicUser,
+        ContactRequest,
+        LoginRequest,
+        ClaimAccountRequest
+    )),
+    modifiers(&SecurityAddon),
+    tags(
+        (name = "mitfahrzentrale", description = "Ride-sharing board for the Schuelerfirma")
+    )
+)]
+pub struct ApiDoc;
+
+struct SecurityAddon;
+
+impl Modify for SecurityAddon {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let Some(components) = openapi.components.as_mut() else {
+            return;
+        };
+
+        components.add_security_scheme(
+            "bearer_auth",
+            SecurityScheme::Http(
+                HttpBuilder::new()
+                    .scheme(HttpAuthScheme::Bearer)
+                    .bearer_format("JWT")
+                    .build(),
+            ),
+        );
+    }
+}
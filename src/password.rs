@@ -0,0 +1,36 @@
+use std::sync::OnceLock;
+
+use argon2::password_hash::rand_core::OsRng;
+use argon2::password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString};
+use argon2::Argon2;
+
+/// Hashes a plaintext password for storage in `schueler.password_hash`.
+pub fn hash(plaintext: &str) -> String {
+    let salt = SaltString::generate(&mut OsRng);
+    Argon2::default()
+        .hash_password(plaintext.as_bytes(), &salt)
+        .expect("argon2 hashing should not fail")
+        .to_string()
+}
+
+/// Verifies a plaintext password against a stored hash. Returns `false`
+/// (rather than propagating an error) on a malformed hash so callers can
+/// treat every failure mode as "wrong credentials".
+pub fn verify(plaintext: &str, hash: &str) -> bool {
+    let Ok(parsed) = PasswordHash::new(hash) else {
+        return false;
+    };
+
+    Argon2::default()
+        .verify_password(plaintext.as_bytes(), &parsed)
+        .is_ok()
+}
+
+/// A hash of no particular password, for callers to `verify` against when
+/// no matching user was found — so a wrong-password response and a
+/// no-such-email response both pay for one argon2 hash and can't be told
+/// apart by timing.
+pub fn dummy_hash() -> &'static str {
+    static HASH: OnceLock<String> = OnceLock::new();
+    HASH.get_or_init(|| hash("not-a-real-password")).as_str()
+}
@@ -0,0 +1,79 @@
+use sqlx::PgPool;
+
+use crate::error::Error;
+use crate::{Entry, EntryContact, NewEntry};
+
+/// Listing, lookup, creation and the `eintrag`→`schueler` contact join for
+/// ride-sharing entries.
+#[derive(Clone)]
+pub struct EntryRepository {
+    pool: PgPool,
+}
+
+impl EntryRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn list(&self) -> Result<Vec<Entry>, Error> {
+        let entries = sqlx::query_as::<_, Entry>(
+            r#"
+            SELECT id, titel, nachricht, typ, sitzplaetze
+            FROM eintrag
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(entries)
+    }
+
+    pub async fn find_by_id(&self, id: i32) -> Result<Entry, Error> {
+        let entry = sqlx::query_as::<_, Entry>(
+            r#"
+            SELECT id, titel, nachricht, typ, sitzplaetze
+            FROM eintrag
+            WHERE id=$1
+            "#,
+        )
+        .bind(id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(entry)
+    }
+
+    pub async fn create(&self, new: NewEntry, author_id: i32) -> Result<Entry, Error> {
+        let entry = sqlx::query_as::<_, Entry>(
+            r#"
+            INSERT INTO eintrag (titel, nachricht, typ, sitzplaetze, schueler_id)
+            VALUES ($1, $2, $3, $4, $5)
+            RETURNING id, titel, nachricht, typ, sitzplaetze
+            "#,
+        )
+        .bind(new.titel)
+        .bind(new.nachricht)
+        .bind(new.typ)
+        .bind(new.sitzplaetze)
+        .bind(author_id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(entry)
+    }
+
+    pub async fn contact_email(&self, entry_id: i32) -> Result<EntryContact, Error> {
+        let contact = sqlx::query_as::<_, EntryContact>(
+            r#"
+            SELECT schueler.email
+            FROM eintrag
+            inner join schueler on eintrag.schueler_id=schueler.id where eintrag.id=$1;
+            "#,
+        )
+        .bind(entry_id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(contact)
+    }
+}
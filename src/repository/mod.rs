@@ -0,0 +1,5 @@
+mod entry;
+mod user;
+
+pub use entry::EntryRepository;
+pub use user::UserRepository;
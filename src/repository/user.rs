@@ -0,0 +1,81 @@
+use sqlx::PgPool;
+
+use crate::User;
+use crate::error::Error;
+
+/// Lookups against `schueler`: listing, id/email lookups, and the
+/// one-time `claim_token` consumption used by account claiming.
+#[derive(Clone)]
+pub struct UserRepository {
+    pool: PgPool,
+}
+
+impl UserRepository {
+    pub fn new(pool: PgPool) -> Self {
+        Self { pool }
+    }
+
+    pub async fn list(&self) -> Result<Vec<User>, Error> {
+        let users = sqlx::query_as::<_, User>(
+            r#"
+            SELECT *
+            FROM schueler
+            "#,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        Ok(users)
+    }
+
+    pub async fn find_by_id(&self, id: i32) -> Result<User, Error> {
+        let user = sqlx::query_as::<_, User>(
+            r#"
+            SELECT * from schueler
+            WHERE id=$1
+            "#,
+        )
+        .bind(id)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(user)
+    }
+
+    pub async fn find_by_email(&self, email: &str) -> Result<User, Error> {
+        let user = sqlx::query_as::<_, User>(
+            r#"
+            SELECT * from schueler
+            WHERE email=$1
+            "#,
+        )
+        .bind(email)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(user)
+    }
+
+    /// Atomically spends `token`: sets `password_hash` and clears `token`
+    /// in one conditional update, so two concurrent claims of the same
+    /// token can't both succeed — the loser's re-checked `token = $2` no
+    /// longer matches the cleared row, so it gets `NotFound`. The
+    /// `token <> ''` guard keeps an empty token from matching rows that
+    /// were already claimed.
+    pub async fn claim_token(&self, token: &str, password_hash: &str) -> Result<User, Error> {
+        let user = sqlx::query_as::<_, User>(
+            r#"
+            UPDATE schueler
+            SET password_hash = $1, token = ''
+            WHERE token = $2 AND token <> ''
+            RETURNING *
+            "#,
+        )
+        .bind(password_hash)
+        .bind(token)
+        .fetch_one(&self.pool)
+        .await?;
+
+        Ok(user)
+    }
+}
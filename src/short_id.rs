@@ -0,0 +1,42 @@
+use sqids::Sqids;
+
+/// Encodes/decodes internal `eintrag.id` values into opaque, URL-safe
+/// short IDs so entries can't be enumerated by counting up from the API.
+/// Internal DB columns stay plain integers; only the HTTP layer sees the
+/// encoded form.
+#[derive(Clone)]
+pub struct ShortIds(Sqids);
+
+const MIN_LENGTH: u8 = 6;
+
+impl ShortIds {
+    pub fn from_env() -> Self {
+        let alphabet = std::env::var("SQIDS_ALPHABET").ok();
+
+        let mut builder = Sqids::builder().min_length(MIN_LENGTH);
+        if let Some(alphabet) = alphabet {
+            builder = builder.alphabet(alphabet.chars().collect());
+        }
+
+        Self(builder.build().expect("invalid sqids alphabet"))
+    }
+
+    pub fn encode(&self, id: i32) -> String {
+        self.0
+            .encode(&[id as u64])
+            .expect("entry id should always encode")
+    }
+
+    /// Decodes a short ID back into the internal `i32`. Returns `None` for
+    /// an empty, undecodable, or otherwise malformed slug.
+    pub fn decode(&self, slug: &str) -> Option<i32> {
+        if slug.is_empty() {
+            return None;
+        }
+
+        match self.0.decode(slug).as_slice() {
+            [id] => i32::try_from(*id).ok(),
+            _ => None,
+        }
+    }
+}